@@ -1,17 +1,16 @@
-use crate::StackBlurrable;
-use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
-
+mod channels;
 mod serial;
 mod simd;
 
-pub use serial::BlurU32;
-pub use simd::u32xN;
+pub use channels::Channels;
+pub use serial::{BlurF32, BlurU32};
+pub use simd::{f32xN, u32xN};
 
 use std::simd::{LaneCount, Simd, SupportedLaneCount};
 
-#[repr(transparent)]
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
-pub struct Argb<T: StackBlurrable>([T; 4]);
+/// A 4-channel alpha-red-green-blue pixel, the layout
+/// [`blur_argb`][crate::blur_argb] and friends operate on.
+pub type Argb<T> = Channels<T, 4>;
 
 impl From<u32> for Argb<BlurU32> {
     fn from(argb: u32) -> Self {
@@ -53,57 +52,167 @@ where
     }
 }
 
-impl<T: StackBlurrable> Add for Argb<T> {
-    type Output = Self;
+impl From<u32> for Argb<BlurF32> {
+    fn from(argb: u32) -> Self {
+        let [a, r, g, b] = argb.to_be_bytes();
+        let cvt = |i: u8| BlurF32(i as f32);
+        Self([cvt(a), cvt(r), cvt(g), cvt(b)])
+    }
+}
+
+impl From<Argb<BlurF32>> for u32 {
+    fn from(Argb([a, r, g, b]): Argb<BlurF32>) -> Self {
+        let cvt = |i: BlurF32| i.0.round() as u8;
+        u32::from_be_bytes([cvt(a), cvt(r), cvt(g), cvt(b)])
+    }
+}
+
+impl<const N: usize> From<[u32; N]> for Argb<f32xN<N>>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    fn from(values: [u32; N]) -> Self {
+        let arrs: [[u8; 4]; N] = values.map(u32::to_be_bytes);
+        Self([
+            f32xN(Simd::from_array(arrs.map(|a| a[0] as f32))),
+            f32xN(Simd::from_array(arrs.map(|a| a[1] as f32))),
+            f32xN(Simd::from_array(arrs.map(|a| a[2] as f32))),
+            f32xN(Simd::from_array(arrs.map(|a| a[3] as f32))),
+        ])
+    }
+}
+
+impl<const N: usize> From<Argb<f32xN<N>>> for [u32; N]
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    fn from(value: Argb<f32xN<N>>) -> Self {
+        let [a, r, g, b] = value.0.map(|i| i.0.to_array());
+        std::array::from_fn(|i| {
+            u32::from_be_bytes([a[i], r[i], g[i], b[i]].map(|x| x.round() as u8))
+        })
+    }
+}
+
+/// A 3-channel RGB pixel, stored as a `[u8; 3]` buffer element.
+pub type Rgb<T> = Channels<T, 3>;
+
+/// A single-channel grayscale pixel.
+pub type Gray<T> = Channels<T, 1>;
+
+impl From<[u8; 3]> for Rgb<BlurU32> {
+    fn from([r, g, b]: [u8; 3]) -> Self {
+        let cvt = |i: u8| BlurU32(i as u32);
+        Self([cvt(r), cvt(g), cvt(b)])
+    }
+}
+
+impl From<Rgb<BlurU32>> for [u8; 3] {
+    fn from(Rgb([r, g, b]): Rgb<BlurU32>) -> Self {
+        let cvt = |i: BlurU32| i.0 as u8;
+        [cvt(r), cvt(g), cvt(b)]
+    }
+}
 
-    fn add(mut self, rhs: Self) -> Self::Output {
-        self += rhs;
-        self
+impl From<u8> for Gray<BlurU32> {
+    fn from(gray: u8) -> Self {
+        Self([BlurU32(gray as u32)])
     }
 }
 
-impl<T: StackBlurrable> Sub for Argb<T> {
-    type Output = Self;
+impl From<Gray<BlurU32>> for u8 {
+    fn from(Gray([gray]): Gray<BlurU32>) -> Self {
+        gray.0 as u8
+    }
+}
 
-    fn sub(mut self, rhs: Self) -> Self::Output {
-        self -= rhs;
-        self
+// `u16` channels range up to 65535, not 255, so `BlurU32`'s plain 32-bit
+// accumulator would hit its overflow ceiling ~16x sooner than for 8-bit
+// data (see `BlurF32`'s docs). Route 16-bit grayscale through the `f32`
+// accumulator instead, which has no overflow ceiling on the radius.
+impl From<u16> for Gray<BlurF32> {
+    fn from(gray: u16) -> Self {
+        Self([BlurF32(gray as f32)])
     }
 }
 
-impl<T: StackBlurrable> AddAssign for Argb<T> {
-    fn add_assign(&mut self, rhs: Self) {
-        let [a, r, g, b] = rhs.0;
-        self.0[0] += a;
-        self.0[1] += r;
-        self.0[2] += g;
-        self.0[3] += b;
+impl From<Gray<BlurF32>> for u16 {
+    fn from(Gray([gray]): Gray<BlurF32>) -> Self {
+        gray.0.round() as u16
     }
 }
 
-impl<T: StackBlurrable> SubAssign for Argb<T> {
-    fn sub_assign(&mut self, rhs: Self) {
-        let [a, r, g, b] = rhs.0;
-        self.0[0] -= a;
-        self.0[1] -= r;
-        self.0[2] -= g;
-        self.0[3] -= b;
+impl Argb<BlurU32> {
+    /// Converts a packed ARGB pixel into premultiplied-alpha form, scaling
+    /// each of R, G, B by `A / 255`.
+    ///
+    /// Blurring in this form keeps the (often black) color of
+    /// fully-transparent pixels from bleeding into visible neighbors, which
+    /// is what produces dark halos when blurring straight-alpha ARGB.
+    pub fn premultiply(argb: u32) -> Self {
+        let Argb([a, r, g, b]) = Self::from(argb);
+        let scale = |c: BlurU32| BlurU32(c.0 * a.0 / 255);
+        Self([a, scale(r), scale(g), scale(b)])
+    }
+
+    /// Converts a premultiplied-alpha pixel (as produced by
+    /// [`premultiply`][Self::premultiply]) back to straight-alpha packed
+    /// ARGB, dividing R, G, B back out by `A / 255`.
+    pub fn unpremultiply(self) -> u32 {
+        let Argb([a, r, g, b]) = self;
+        // Premultiplied R/G/B are already zero wherever A is zero, so any
+        // positive divisor gives the correct (transparent) result here.
+        let divisor = a.0.max(1);
+        let unscale = |c: BlurU32| BlurU32((c.0 * 255) / divisor);
+        Argb([a, unscale(r), unscale(g), unscale(b)]).into()
     }
 }
 
-impl<T: StackBlurrable> Mul<usize> for Argb<T> {
-    type Output = Self;
+impl<const N: usize> Argb<u32xN<N>>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// SIMD version of [`Argb::<BlurU32>::premultiply`](Argb::premultiply).
+    pub fn premultiply(argb: [u32; N]) -> Self {
+        let Argb([a, r, g, b]) = Self::from(argb);
+        let a_arr = a.0.to_array();
+        let scale = |c: u32xN<N>| {
+            let c_arr = c.0.to_array();
+            u32xN(Simd::from_array(std::array::from_fn(|i| {
+                c_arr[i] * a_arr[i] / 255
+            })))
+        };
+        Self([a, scale(r), scale(g), scale(b)])
+    }
+
+    /// SIMD version of [`Argb::<BlurU32>::unpremultiply`](Argb::unpremultiply).
+    pub fn unpremultiply(self) -> [u32; N] {
+        let Argb([a, r, g, b]) = self;
+        let a_arr = a.0.to_array().map(|x| x.max(1));
+        let unscale = |c: u32xN<N>| {
+            let c_arr = c.0.to_array();
+            u32xN(Simd::from_array(std::array::from_fn(|i| {
+                c_arr[i] * 255 / a_arr[i]
+            })))
+        };
+        Argb([a, unscale(r), unscale(g), unscale(b)]).into()
+    }
+}
 
-    fn mul(self, rhs: usize) -> Self::Output {
-        let [a, r, g, b] = self.0;
-        Self([a * rhs, r * rhs, g * rhs, b * rhs])
+// As with `Gray<_>` above, `u16` channels need the unbounded-range `f32`
+// accumulator: `BlurU32` would only be correct up to roughly radius 256 for
+// 16-bit data, far below the radius <= 4096 ceiling the rest of the crate's
+// ARGB helpers document.
+impl From<[u16; 4]> for Argb<BlurF32> {
+    fn from([a, r, g, b]: [u16; 4]) -> Self {
+        let cvt = |i: u16| BlurF32(i as f32);
+        Self([cvt(a), cvt(r), cvt(g), cvt(b)])
     }
 }
 
-impl<T: StackBlurrable> Div<usize> for Argb<T> {
-    type Output = Self;
-    fn div(self, rhs: usize) -> Self::Output {
-        let [a, r, g, b] = self.0;
-        Self([a / rhs, r / rhs, g / rhs, b / rhs])
+impl From<Argb<BlurF32>> for [u16; 4] {
+    fn from(Argb([a, r, g, b]): Argb<BlurF32>) -> Self {
+        let cvt = |i: BlurF32| i.0.round() as u16;
+        [cvt(a), cvt(r), cvt(g), cvt(b)]
     }
 }
@@ -0,0 +1,70 @@
+//! Generic fixed-width pixel channel storage, parameterized over both the
+//! per-channel accumulator type and the number of channels.
+
+use crate::StackBlurrable;
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
+/// `C` channels of a pixel, each stored as a [`StackBlurrable`] accumulator.
+///
+/// [`Argb`][super::Argb] is simply `Channels<T, 4>`; this type is also the
+/// basis for other pixel layouts such as RGB, grayscale, and
+/// 16-bit-per-channel RGBA.
+#[repr(transparent)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Channels<T: StackBlurrable, const C: usize>(pub(super) [T; C]);
+
+impl<T: StackBlurrable, const C: usize> Default for Channels<T, C> {
+    fn default() -> Self {
+        Self([T::default(); C])
+    }
+}
+
+impl<T: StackBlurrable, const C: usize> Add for Channels<T, C> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T: StackBlurrable, const C: usize> Sub for Channels<T, C> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<T: StackBlurrable, const C: usize> AddAssign for Channels<T, C> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<T: StackBlurrable, const C: usize> SubAssign for Channels<T, C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<T: StackBlurrable, const C: usize> Mul<usize> for Channels<T, C> {
+    type Output = Self;
+
+    fn mul(self, rhs: usize) -> Self::Output {
+        Self(self.0.map(|v| v * rhs))
+    }
+}
+
+impl<T: StackBlurrable, const C: usize> Div<usize> for Channels<T, C> {
+    type Output = Self;
+
+    fn div(self, rhs: usize) -> Self::Output {
+        Self(self.0.map(|v| v / rhs))
+    }
+}
@@ -47,3 +47,58 @@ impl Div<usize> for BlurU32 {
         Self(self.0.wrapping_div(rhs as u32))
     }
 }
+
+/// A floating-point [`StackBlurrable`][crate::StackBlurrable] accumulator.
+///
+/// Unlike [`BlurU32`], sums are kept in `f32` rather than wrapping 32-bit
+/// integer arithmetic, so they never overflow regardless of radius — see
+/// [`StackBlurrable`][crate::StackBlurrable]'s docs for the
+/// exactness/radius-range tradeoff this makes, rounding to the pixel format
+/// only once, on write-out.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct BlurF32(pub f32);
+
+impl Add for BlurF32 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for BlurF32 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl AddAssign for BlurF32 {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for BlurF32 {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Mul<usize> for BlurF32 {
+    type Output = Self;
+
+    fn mul(self, rhs: usize) -> Self::Output {
+        Self(self.0 * rhs as f32)
+    }
+}
+
+impl Div<usize> for BlurF32 {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: usize) -> Self::Output {
+        Self(self.0 / rhs as f32)
+    }
+}
@@ -71,3 +71,75 @@ where
         ))
     }
 }
+
+/// A floating-point SIMD [`StackBlurrable`][crate::StackBlurrable]
+/// accumulator, the vectorized counterpart to
+/// [`BlurF32`][super::serial::BlurF32] — see its docs for the
+/// exactness/radius-range tradeoff this makes.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+#[allow(non_camel_case_types)]
+pub struct f32xN<const N: usize>(pub Simd<f32, N>)
+where
+    LaneCount<N>: SupportedLaneCount;
+
+impl<const N: usize> Add for f32xN<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl<const N: usize> Sub for f32xN<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl<const N: usize> AddAssign for f32xN<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl<const N: usize> SubAssign for f32xN<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl<const N: usize> Mul<usize> for f32xN<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: usize) -> Self::Output {
+        Self(self.0 * Simd::<f32, N>::splat(rhs as f32))
+    }
+}
+
+impl<const N: usize> Div<usize> for f32xN<N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    type Output = Self;
+
+    fn div(self, rhs: usize) -> Self::Output {
+        Self(self.0 / Simd::<f32, N>::splat(rhs as f32))
+    }
+}
@@ -60,6 +60,8 @@ mod test;
 
 pub mod color;
 pub mod iter;
+#[cfg(feature = "rayon")]
+pub mod par;
 pub mod traits;
 
 use color::Argb;
@@ -73,21 +75,34 @@ use traits::StackBlurrable;
 pub fn blur<T, B: StackBlurrable>(
     buffer: &mut ImgRefMut<T>,
     radius: usize,
+    to_blurrable: impl FnMut(&T) -> B,
+    to_pixel: impl FnMut(B) -> T,
+) {
+    let mut ops = VecDeque::new();
+    blur_with_ops(buffer, radius, &mut ops, to_blurrable, to_pixel);
+}
+
+/// Core of [`blur`], taking the `ops` scratch buffer as a parameter so
+/// callers that need to run several passes (like [`gaussian_blur`]) can
+/// reuse the same allocation across all of them instead of paying for a
+/// fresh one per pass.
+fn blur_with_ops<T, B: StackBlurrable>(
+    buffer: &mut ImgRefMut<T>,
+    radius: usize,
+    ops: &mut VecDeque<B>,
     mut to_blurrable: impl FnMut(&T) -> B,
     mut to_pixel: impl FnMut(B) -> T,
 ) {
     use imgref_iter::iter::{IterWindows, IterWindowsPtrMut};
     use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
 
-    let mut ops = VecDeque::new();
-
     // This is needed to avoid Undefined Behavior. Writing to the rows of the
     // must be done before constructing the columns iterators, because otherwise
     // the writes would invalidate their borrows. However I don't want to
     // duplicate this loop, so make it a closure.
     let mut blur_windows = |writer: IterWindowsPtrMut<T>, reader: IterWindows<T>| {
         for (write, read) in writer.zip(reader) {
-            let mut blur = StackBlur::new(read.map(&mut to_blurrable), radius, &mut ops);
+            let mut blur = StackBlur::new(read.map(&mut to_blurrable), radius, ops);
             write.for_each(|place| unsafe { *place = to_pixel(blur.next().unwrap()) });
         }
     };
@@ -203,3 +218,292 @@ where
         Argb::into,
     );
 }
+
+/// Blurs a buffer of 3-channel `[u8; 3]` RGB pixels.
+///
+/// This is a version of [`blur`] with pre-filled conversion routines, the
+/// RGB counterpart to [`blur_argb`].
+pub fn blur_rgb(buffer: &mut ImgRefMut<[u8; 3]>, radius: usize) {
+    blur(buffer, radius, |i| color::Rgb::from(*i), color::Rgb::into);
+}
+
+/// Blurs a buffer of single-channel grayscale pixels.
+///
+/// This is a version of [`blur`] with pre-filled conversion routines,
+/// generic over any pixel type `T` and accumulator `B` with a
+/// [`Gray<B>`][color::Gray] conversion — `u8` via [`BlurU32`][color::BlurU32]
+/// and `u16` via [`BlurF32`][color::BlurF32] (see its docs for why `u16`
+/// needs the float accumulator). Callers pick `B` with a turbofish, e.g.
+/// `blur_gray::<u16, BlurF32>(buffer, radius)`.
+pub fn blur_gray<T: Copy, B: StackBlurrable>(buffer: &mut ImgRefMut<T>, radius: usize)
+where
+    color::Channels<B, 1>: From<T> + Into<T>,
+{
+    blur(
+        buffer,
+        radius,
+        |i: &T| color::Channels::from(*i),
+        color::Channels::into,
+    );
+}
+
+/// Blurs a buffer of 4-channel `[u16; 4]` RGBA pixels.
+///
+/// This is a version of [`blur`] with pre-filled conversion routines, the
+/// 16-bit-per-channel counterpart to [`blur_argb`]. Uses the `f32`
+/// accumulator (see [`BlurF32`][color::BlurF32]) since 16-bit channel values
+/// would overflow `BlurU32`'s 32-bit wrapping sum at far lower radii than
+/// 8-bit ARGB does.
+pub fn blur_rgba16(buffer: &mut ImgRefMut<[u16; 4]>, radius: usize) {
+    blur(
+        buffer,
+        radius,
+        |i| Argb::<color::BlurF32>::from(*i),
+        Argb::into,
+    );
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) using an `f32`
+/// accumulator.
+///
+/// This is a version of [`blur_argb`] built on
+/// [`BlurF32`][color::BlurF32] rather than [`BlurU32`][color::BlurU32] —
+/// see its docs for the exactness/radius-range tradeoff this makes.
+pub fn blur_argb_f32(buffer: &mut ImgRefMut<u32>, radius: usize) {
+    blur(
+        buffer,
+        radius,
+        |i| Argb::<color::BlurF32>::from(*i),
+        Argb::into,
+    );
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD, using
+/// an `f32` accumulator.
+///
+/// This is a version of [`simd_blur_argb`] built on
+/// [`f32xN`][color::f32xN] rather than [`u32xN`][color::u32xN] — see
+/// [`BlurF32`][color::BlurF32]'s docs for the exactness/radius-range
+/// tradeoff this makes.
+pub fn simd_blur_argb_f32<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize)
+where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    simd_blur(
+        buffer,
+        radius,
+        |i: [&u32; LANES]| Argb::<color::f32xN<LANES>>::from(i.map(u32::clone)),
+        Argb::into,
+        |i| Argb::<color::BlurF32>::from(*i),
+        Argb::into,
+    );
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in
+/// premultiplied-alpha space.
+///
+/// `blur_argb` blurs alpha and color channels independently, which bleeds
+/// the (often black) color of fully-transparent pixels into visible
+/// neighbors and produces dark halos. This version converts to and from
+/// premultiplied alpha around the blur, which gives correct
+/// compositing-aware results for images with an alpha channel.
+pub fn blur_argb_premultiplied(buffer: &mut ImgRefMut<u32>, radius: usize) {
+    blur(
+        buffer,
+        radius,
+        |i| Argb::<color::BlurU32>::premultiply(*i),
+        Argb::<color::BlurU32>::unpremultiply,
+    );
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD, in
+/// premultiplied-alpha space.
+///
+/// See [`blur_argb_premultiplied`] for why this avoids dark halos around
+/// transparent regions.
+pub fn simd_blur_argb_premultiplied<const LANES: usize>(
+    buffer: &mut ImgRefMut<u32>,
+    radius: usize,
+) where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    simd_blur(
+        buffer,
+        radius,
+        |i: [&u32; LANES]| Argb::<color::u32xN<LANES>>::premultiply(i.map(u32::clone)),
+        Argb::<color::u32xN<LANES>>::unpremultiply,
+        |i| Argb::<color::BlurU32>::premultiply(*i),
+        Argb::<color::BlurU32>::unpremultiply,
+    );
+}
+
+/// Computes the `n` box-blur radii that approximate a Gaussian blur of
+/// standard deviation `sigma`, following the standard "almost-Gaussian via
+/// repeated box passes" derivation (see Ivan Kutskir's fast Gaussian blur
+/// write-up, also used by StackBlur implementations elsewhere).
+///
+/// Returns an empty `Vec` for `n == 0` (zero passes blur nothing), which
+/// also avoids dividing by zero in the box-size math below.
+fn gaussian_radii(sigma: f64, n: usize) -> Vec<usize> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let nf = n as f64;
+    let w_ideal = (12.0 * sigma * sigma / nf + 1.0).sqrt();
+    let mut wl = w_ideal.floor() as isize;
+    if wl % 2 == 0 {
+        wl -= 1;
+    }
+    let wu = wl + 2;
+    let m_ideal = (12.0 * sigma * sigma - nf * (wl * wl) as f64 - 4.0 * nf * wl as f64 - 3.0 * nf)
+        / (-4.0 * wl as f64 - 4.0);
+    let m = m_ideal.round() as usize;
+
+    (0..n)
+        .map(|i| if i < m { wl } else { wu })
+        .map(|boxwidth| ((boxwidth - 1) / 2) as usize)
+        .collect()
+}
+
+/// Blurs a buffer with a Gaussian blur of standard deviation `sigma`,
+/// approximated by running `n` [`blur`] passes of carefully chosen radii in
+/// sequence.
+///
+/// Since a single StackBlur pass already applies a triangular (two-box)
+/// weighting, `n = 2` or `n = 3` passes give results very close to a true
+/// Gaussian blur; larger `n` trades speed for further accuracy.
+pub fn gaussian_blur<T, B: StackBlurrable>(
+    buffer: &mut ImgRefMut<T>,
+    sigma: f64,
+    n: usize,
+    mut to_blurrable: impl FnMut(&T) -> B,
+    mut to_pixel: impl FnMut(B) -> T,
+) {
+    let mut ops = VecDeque::new();
+    for radius in gaussian_radii(sigma, n) {
+        blur_with_ops(buffer, radius, &mut ops, &mut to_blurrable, &mut to_pixel);
+    }
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with a Gaussian
+/// blur of standard deviation `sigma`.
+///
+/// This is a version of [`gaussian_blur`] with pre-filled conversion
+/// routines that provide good results for blur radii <= 4096. Larger radii
+/// may overflow; see [`blur_argb`].
+pub fn gaussian_blur_argb(buffer: &mut ImgRefMut<u32>, sigma: f64, n: usize) {
+    gaussian_blur(
+        buffer,
+        sigma,
+        n,
+        |i| Argb::<color::BlurU32>::from(*i),
+        Argb::into,
+    );
+}
+
+/// Probes the widest portable-SIMD lane count the running CPU actually
+/// supports, so callers don't have to pick one ahead of time via cfg.
+///
+/// Returns `16` when AVX-512 is available, `8` for AVX2, `4` for SSE2 or
+/// NEON (both 128-bit, 4 lanes of `u32`/`f32`), or `0` if no usable SIMD
+/// extension was detected (in which case callers should fall back to the
+/// scalar path).
+fn simd_width() -> usize {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx512f") {
+            return 16;
+        }
+        if is_x86_feature_detected!("avx2") {
+            return 8;
+        }
+        if is_x86_feature_detected!("sse2") {
+            return 4;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return 4;
+        }
+    }
+    0
+}
+
+/// Runtime lane-width-dispatching counterpart to [`simd_blur`].
+///
+/// Instead of forcing callers to hardcode a lane count at compile time,
+/// this probes [`simd_width`] and dispatches into whichever of the 16/8/4
+/// lane monomorphizations of [`simd_blur`] the CPU actually supports,
+/// falling back to the scalar [`blur`] when no SIMD is usable.
+#[allow(clippy::too_many_arguments)]
+pub fn simd_blur_auto<
+    T,
+    B16: StackBlurrable,
+    B8: StackBlurrable,
+    B4: StackBlurrable,
+    Bsingle: StackBlurrable,
+>(
+    buffer: &mut ImgRefMut<T>,
+    radius: usize,
+    to_blurrable_16: impl FnMut([&T; 16]) -> B16,
+    to_pixel_16: impl FnMut(B16) -> [T; 16],
+    to_blurrable_8: impl FnMut([&T; 8]) -> B8,
+    to_pixel_8: impl FnMut(B8) -> [T; 8],
+    to_blurrable_4: impl FnMut([&T; 4]) -> B4,
+    to_pixel_4: impl FnMut(B4) -> [T; 4],
+    mut to_blurrable_single: impl FnMut(&T) -> Bsingle,
+    mut to_pixel_single: impl FnMut(Bsingle) -> T,
+) {
+    match simd_width() {
+        16 => simd_blur(
+            buffer,
+            radius,
+            to_blurrable_16,
+            to_pixel_16,
+            to_blurrable_single,
+            to_pixel_single,
+        ),
+        8 => simd_blur(
+            buffer,
+            radius,
+            to_blurrable_8,
+            to_pixel_8,
+            to_blurrable_single,
+            to_pixel_single,
+        ),
+        4 => simd_blur(
+            buffer,
+            radius,
+            to_blurrable_4,
+            to_pixel_4,
+            to_blurrable_single,
+            to_pixel_single,
+        ),
+        _ => blur(buffer, radius, &mut to_blurrable_single, &mut to_pixel_single),
+    }
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD,
+/// automatically selecting the widest lane count the running CPU supports.
+///
+/// This is a version of [`simd_blur_argb`] that mirrors
+/// [`simd_blur_argb::<8>`][simd_blur_argb] and friends but, rather than
+/// pinning a lane count at compile time, probes the target at runtime via
+/// [`simd_blur_auto`] and falls back to [`blur_argb`] on CPUs without usable
+/// SIMD.
+pub fn simd_blur_argb_auto(buffer: &mut ImgRefMut<u32>, radius: usize) {
+    simd_blur_auto(
+        buffer,
+        radius,
+        |i: [&u32; 16]| Argb::<color::u32xN<16>>::from(i.map(u32::clone)),
+        Argb::into,
+        |i: [&u32; 8]| Argb::<color::u32xN<8>>::from(i.map(u32::clone)),
+        Argb::into,
+        |i: [&u32; 4]| Argb::<color::u32xN<4>>::from(i.map(u32::clone)),
+        Argb::into,
+        |i| Argb::<color::BlurU32>::from(*i),
+        Argb::into,
+    );
+}
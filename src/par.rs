@@ -0,0 +1,137 @@
+//! Deterministic parallel blur, gated behind the `rayon` feature.
+//!
+//! Each row (and, once all rows are done, each column) is a fully
+//! independent [`StackBlur`] scan over disjoint output memory, so the rows
+//! can be distributed across a thread pool with no risk of two workers
+//! touching the same pixel. Each worker reuses its own `ops` scratch
+//! `VecDeque` across every row (or column) it handles, via rayon's
+//! `for_each_init`, so the result is bit-for-bit identical no matter how
+//! many threads are used to compute it. The horizontal phase still fully
+//! completes before the vertical phase begins, matching the ordering
+//! invariant of the serial [`blur`][crate::blur].
+
+use std::collections::VecDeque;
+use std::simd::{LaneCount, SupportedLaneCount};
+
+use imgref::ImgRefMut;
+use imgref_iter::iter::{
+    IterWindows, IterWindowsPtrMut, SimdIterWindow, SimdIterWindowPtrMut, SimdIterWindows,
+    SimdIterWindowsPtrMut,
+};
+use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut, ImgSimdIter, ImgSimdIterPtrMut};
+use rayon::prelude::*;
+
+use crate::color::Argb;
+use crate::iter::StackBlur;
+use crate::traits::StackBlurrable;
+
+/// Parallel version of [`blur`][crate::blur].
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+pub fn par_blur<T: Sync, B: StackBlurrable + Send>(
+    buffer: &mut ImgRefMut<T>,
+    radius: usize,
+    to_blurrable: impl Fn(&T) -> B + Sync,
+    to_pixel: impl Fn(B) -> T + Sync,
+) {
+    let par_blur_windows = |writer: IterWindowsPtrMut<T>, reader: IterWindows<T>| {
+        writer
+            .zip(reader)
+            .par_bridge()
+            .for_each_init(VecDeque::new, |ops, (write, read)| {
+                let mut blur = StackBlur::new(read.map(&to_blurrable), radius, ops);
+                write.for_each(|place| unsafe { *place = to_pixel(blur.next().unwrap()) });
+            });
+    };
+
+    let buffer_ptr = buffer.as_mut_ptr();
+    par_blur_windows(
+        unsafe { buffer_ptr.iter_rows_ptr_mut() },
+        buffer.iter_rows(),
+    );
+    par_blur_windows(
+        unsafe { buffer_ptr.iter_cols_ptr_mut() },
+        buffer.iter_cols(),
+    );
+}
+
+/// Parallel version of [`simd_blur`][crate::simd_blur].
+pub fn par_simd_blur<
+    T: Sync,
+    Bsimd: StackBlurrable + Send,
+    Bsingle: StackBlurrable + Send,
+    const LANES: usize,
+>(
+    buffer: &mut ImgRefMut<T>,
+    radius: usize,
+    to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+    to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+    to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+    to_pixel_single: impl Fn(Bsingle) -> T + Sync,
+) where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    let par_simd_blur_windows = |writer: SimdIterWindowsPtrMut<T, LANES>,
+                                  reader: SimdIterWindows<T, LANES>| {
+        writer.zip(reader).par_bridge().for_each_init(
+            || (VecDeque::new(), VecDeque::new()),
+            |(ops_simd, ops_single), (write, read)| match (write, read) {
+                (SimdIterWindowPtrMut::Simd(write), SimdIterWindow::Simd(read)) => {
+                    let mut blur = StackBlur::new(read.map(&to_blurrable_simd), radius, ops_simd);
+                    write.for_each(|place| {
+                        place
+                            .into_iter()
+                            .zip(to_pixel_simd(blur.next().unwrap()))
+                            .for_each(|(place, pixel)| unsafe { *place = pixel });
+                    });
+                }
+
+                (SimdIterWindowPtrMut::Single(write), SimdIterWindow::Single(read)) => {
+                    let mut blur =
+                        StackBlur::new(read.map(&to_blurrable_single), radius, ops_single);
+                    write.for_each(|place| unsafe {
+                        *place = to_pixel_single(blur.next().unwrap());
+                    });
+                }
+
+                _ => unreachable!(),
+            },
+        );
+    };
+
+    let buffer_ptr = buffer.as_mut_ptr();
+    par_simd_blur_windows(
+        unsafe { buffer_ptr.simd_iter_rows_ptr_mut::<LANES>() },
+        buffer.simd_iter_rows::<LANES>(),
+    );
+    par_simd_blur_windows(
+        unsafe { buffer_ptr.simd_iter_cols_ptr_mut::<LANES>() },
+        buffer.simd_iter_cols::<LANES>(),
+    );
+}
+
+/// Parallel version of [`blur_argb`][crate::blur_argb].
+pub fn par_blur_argb(buffer: &mut ImgRefMut<u32>, radius: usize) {
+    par_blur(
+        buffer,
+        radius,
+        |i| Argb::<crate::color::BlurU32>::from(*i),
+        Argb::into,
+    );
+}
+
+/// Parallel version of [`simd_blur_argb`][crate::simd_blur_argb].
+pub fn par_simd_blur_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize)
+where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    par_simd_blur(
+        buffer,
+        radius,
+        |i: [&u32; LANES]| Argb::<crate::color::u32xN<LANES>>::from(i.map(u32::clone)),
+        Argb::into,
+        |i| Argb::<crate::color::BlurU32>::from(*i),
+        Argb::into,
+    );
+}
@@ -47,3 +47,48 @@ fn simd_blur_argb_1024(bencher: &mut Bencher) {
     let mut buf = ImgVec::new(vec![0; WIDTH * HEIGHT], WIDTH, HEIGHT);
     bencher.iter(|| crate::simd_blur_argb::<8>(&mut buf.as_mut(), 1024));
 }
+
+#[test]
+fn gaussian_radii_zero_passes_is_empty() {
+    assert_eq!(crate::gaussian_radii(5.0, 0), Vec::<usize>::new());
+}
+
+#[test]
+fn gaussian_radii_returns_n_positive_radii() {
+    let radii = crate::gaussian_radii(5.0, 3);
+    assert_eq!(radii.len(), 3);
+    assert!(radii.iter().all(|&r| r > 0));
+}
+
+#[test]
+fn premultiply_round_trips_opaque_pixels_exactly() {
+    use crate::color::{Argb, BlurU32};
+
+    let argb = 0xff3366cc;
+    assert_eq!(Argb::<BlurU32>::unpremultiply(Argb::<BlurU32>::premultiply(argb)), argb);
+}
+
+#[test]
+fn premultiply_collapses_fully_transparent_pixels_to_zero() {
+    use crate::color::{Argb, BlurU32};
+
+    // Any RGB behind a fully-transparent pixel is indistinguishable once
+    // premultiplied, and unpremultiply must not divide by a zero alpha.
+    assert_eq!(Argb::<BlurU32>::unpremultiply(Argb::<BlurU32>::premultiply(0x00123456)), 0);
+}
+
+#[test]
+fn premultiply_round_trip_is_close_for_partial_alpha() {
+    use crate::color::{Argb, BlurU32};
+
+    let argb = 0x80ff8020;
+    let [a, r, g, b] = Argb::<BlurU32>::unpremultiply(Argb::<BlurU32>::premultiply(argb)).to_be_bytes();
+    let [ea, er, eg, eb] = argb.to_be_bytes();
+    assert_eq!(a, ea);
+    for (got, expected) in [(r, er), (g, eg), (b, eb)] {
+        assert!(
+            got.abs_diff(expected) <= 2,
+            "{got} too far from {expected}"
+        );
+    }
+}
@@ -12,6 +12,12 @@ use std::ops::{Add, AddAssign, Div, Mul, SubAssign};
 /// They should have a significantly higher precision than the pixel format that
 /// they represent, as they may be multiplied by hundreds or thousands before
 /// being divided. They should also ideally be `Copy` so that cloning is cheap.
+///
+/// Integer implementations (like [`BlurU32`][crate::color::BlurU32]) are cheap
+/// but wrap around at large radii; floating-point implementations (like
+/// [`BlurF32`][crate::color::BlurF32]) have no such ceiling, trading a little
+/// exactness — values round to the pixel format only once, on write-out —
+/// for an unbounded radius range.
 pub trait StackBlurrable:
     Default
     + Copy